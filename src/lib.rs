@@ -1,6 +1,7 @@
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
+use rayon::prelude::*;
 use std::fmt;
 
 /// Custom error type for Becker IRR calculations
@@ -9,6 +10,7 @@ pub enum BeckerError {
     MaxIterationsReached(&'static str),
     EmptyEarnings,
     InvalidInput(&'static str),
+    NoRootFound(&'static str),
 }
 
 impl fmt::Display for BeckerError {
@@ -17,6 +19,7 @@ impl fmt::Display for BeckerError {
             BeckerError::MaxIterationsReached(msg) => write!(f, "Max iterations reached: {}", msg),
             BeckerError::EmptyEarnings => write!(f, "Empty earnings sequence"),
             BeckerError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            BeckerError::NoRootFound(msg) => write!(f, "No root found: {}", msg),
         }
     }
 }
@@ -76,18 +79,140 @@ fn becker_obt_rs(earnings: &[f64], disc_rate: f64, becker_irr: f64) -> f64 {
 
 /// Python wrapper for becker_obt
 #[pyfunction]
-fn becker_obt(earnings: Vec<f64>, disc_rate: f64, becker_irr: f64) -> f64 {
-    becker_obt_rs(&earnings, disc_rate, becker_irr)
+fn becker_obt(earnings: Vec<f64>, disc_rate: f64, becker_irr: f64) -> PyResult<f64> {
+    if !disc_rate.is_finite() || !becker_irr.is_finite() || earnings.iter().any(|x| !x.is_finite()) {
+        return Err(BeckerError::InvalidInput(
+            "earnings, disc_rate, and becker_irr must be finite",
+        )
+        .into());
+    }
+
+    Ok(becker_obt_rs(&earnings, disc_rate, becker_irr))
 }
 
-/// Find initial bounds for the IRR calculation
-fn find_bounds(
-    earnings: &[f64],
-    int_disc: f64,
-    initial_guess: f64,
-    config: &IrrConfig,
-) -> BeckerResult {
-    let mut obt = becker_obt_rs(earnings, int_disc, initial_guess);
+/// Calculate the Becker OBt value together with its derivative with
+/// respect to `becker_irr`.
+///
+/// The recurrence `obt_i = obt_{i-1} * factor_i + earning_i` is
+/// differentiated alongside the value itself: when `factor_i` is the
+/// `becker_irr`-dependent branch (`obt_{i-1} < 0.0`), the chain rule adds
+/// `obt_{i-1}` to the running derivative; otherwise the derivative is just
+/// carried forward scaled by the (irr-independent) discount factor.
+#[inline]
+fn becker_obt_with_deriv_rs(earnings: &[f64], disc_rate: f64, becker_irr: f64) -> (f64, f64) {
+    if earnings.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut obt = earnings[0];
+    let mut d_obt = 0.0;
+
+    let pos_factor = 1.0 + disc_rate;
+    let neg_factor = 1.0 + becker_irr;
+
+    earnings.iter().skip(1).for_each(|&earning| {
+        d_obt = if obt < 0.0 {
+            d_obt * neg_factor + obt
+        } else {
+            d_obt * pos_factor
+        };
+        obt = obt * if obt < 0.0 { neg_factor } else { pos_factor } + earning;
+    });
+
+    (obt, d_obt)
+}
+
+/// Python wrapper for becker_obt_with_deriv
+#[pyfunction]
+fn becker_obt_with_deriv(earnings: Vec<f64>, disc_rate: f64, becker_irr: f64) -> PyResult<(f64, f64)> {
+    if !disc_rate.is_finite() || !becker_irr.is_finite() || earnings.iter().any(|x| !x.is_finite()) {
+        return Err(BeckerError::InvalidInput(
+            "earnings, disc_rate, and becker_irr must be finite",
+        )
+        .into());
+    }
+
+    Ok(becker_obt_with_deriv_rs(&earnings, disc_rate, becker_irr))
+}
+
+/// Calculate the Becker OBt value for a series of earnings with irregular
+/// elapsed time between them, XIRR-style.
+///
+/// `periods[i]` is the elapsed time (e.g. fractional years, or day counts
+/// divided by 365) between earning `i - 1` and earning `i`; `periods[0]`
+/// is unused since the first earning has no preceding gap. The growth
+/// factor for each step is still selected by the sign of the running OBt,
+/// but is now raised to the power of the elapsed period so cashflows
+/// dated at irregular intervals compound correctly.
+#[inline]
+fn becker_obt_periods_rs(earnings: &[f64], periods: &[f64], disc_rate: f64, becker_irr: f64) -> f64 {
+    if earnings.is_empty() {
+        return 0.0;
+    }
+
+    let mut obt = earnings[0];
+
+    let pos_factor = 1.0 + disc_rate;
+    let neg_factor = 1.0 + becker_irr;
+
+    earnings
+        .iter()
+        .zip(periods.iter())
+        .skip(1)
+        .for_each(|(&earning, &period)| {
+            let factor = if obt < 0.0 { neg_factor } else { pos_factor };
+            obt = if factor <= 0.0 {
+                // A non-positive growth-factor base (e.g. becker_irr <= -1,
+                // reachable while find_bounds steps the guess downward) is
+                // treated as outside the feasible domain: a negative base
+                // makes `factor.powf(period)` undefined for a fractional
+                // period, so the OBt becomes an infinity of the running
+                // OBt's sign instead of NaN.
+                if obt < 0.0 {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                }
+            } else {
+                obt * factor.powf(period) + earning
+            };
+        });
+
+    obt
+}
+
+/// Python wrapper for becker_obt_periods
+#[pyfunction]
+fn becker_obt_periods(
+    earnings: Vec<f64>,
+    periods: Vec<f64>,
+    disc_rate: f64,
+    becker_irr: f64,
+) -> PyResult<f64> {
+    if earnings.len() != periods.len() {
+        return Err(BeckerError::InvalidInput("earnings and periods must be the same length").into());
+    }
+
+    if !disc_rate.is_finite()
+        || !becker_irr.is_finite()
+        || earnings.iter().any(|x| !x.is_finite())
+        || periods.iter().any(|x| !x.is_finite())
+    {
+        return Err(BeckerError::InvalidInput(
+            "earnings, periods, disc_rate, and becker_irr must be finite",
+        )
+        .into());
+    }
+
+    Ok(becker_obt_periods_rs(&earnings, &periods, disc_rate, becker_irr))
+}
+
+/// Find initial bounds for the IRR calculation, bracketing a root of `f`
+fn find_bounds<F>(mut f: F, initial_guess: f64, config: &IrrConfig) -> BeckerResult
+where
+    F: FnMut(f64) -> f64,
+{
+    let mut obt = f(initial_guess);
 
     // Early exit if initial guess is very close to solution
     if obt.abs() < config.tolerance {
@@ -101,7 +226,7 @@ fn find_bounds(
         // Binary search for bound
         for _ in 0..config.max_iterations {
             irr_b -= step;
-            let new_obt = becker_obt_rs(earnings, int_disc, irr_b);
+            let new_obt = f(irr_b);
 
             if new_obt >= 0.0 {
                 return Ok(irr_b);
@@ -119,7 +244,7 @@ fn find_bounds(
         // Binary search for bound
         for _ in 0..config.max_iterations {
             irr_a += step;
-            let new_obt = becker_obt_rs(earnings, int_disc, irr_a);
+            let new_obt = f(irr_a);
 
             if new_obt <= 0.0 {
                 return Ok(irr_a);
@@ -132,11 +257,138 @@ fn find_bounds(
         }
     }
 
+    Err(BeckerError::NoRootFound(
+        "OBt curve does not cross zero within the search range",
+    ))
+}
+
+/// Root-find `f` on the bracket `[a, b]` using Brent's method.
+///
+/// Combines the guaranteed convergence of bisection with the faster
+/// convergence of inverse quadratic interpolation (falling back to a
+/// secant step when the three ordinates aren't distinct). The OBt curve
+/// has a kink where the growth factor switches sign, so every trial step
+/// is checked against the classic Brent acceptance conditions and a plain
+/// bisection step is used whenever interpolation would leave the bracket
+/// or fail to shrink it fast enough.
+fn brent_solve<F>(mut f: F, mut a: f64, mut b: f64, config: &IrrConfig) -> BeckerResult
+where
+    F: FnMut(f64) -> f64,
+{
+    let mut fa = f(a);
+    let mut fb = f(b);
+
+    if fa * fb > 0.0 {
+        return Err(BeckerError::NoRootFound(
+            "Brent solver: root is not bracketed",
+        ));
+    }
+
+    // Keep b as the best estimate so far (smallest |f|)
+    if fa.abs() < fb.abs() {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+
+    let mut c = a;
+    let mut fc = fa;
+    let mut d = a;
+    let mut mflag = true;
+
+    for _ in 0..config.max_iterations {
+        if fb.abs() < config.tolerance || (b - a).abs() < config.tolerance {
+            return Ok(b);
+        }
+
+        let mut s = if fa != fc && fb != fc {
+            // Inverse quadratic interpolation
+            a * fb * fc / ((fa - fb) * (fa - fc))
+                + b * fa * fc / ((fb - fa) * (fb - fc))
+                + c * fa * fb / ((fc - fa) * (fc - fb))
+        } else {
+            // Secant step
+            b - fb * (b - a) / (fb - fa)
+        };
+
+        let lower = (3.0 * a + b) / 4.0;
+        let (lo, hi) = if lower < b { (lower, b) } else { (b, lower) };
+        let needs_bisection = s < lo
+            || s > hi
+            || (mflag && (s - b).abs() >= (b - c).abs() / 2.0)
+            || (!mflag && (s - b).abs() >= (c - d).abs() / 2.0)
+            || (mflag && (b - c).abs() < config.tolerance)
+            || (!mflag && (c - d).abs() < config.tolerance);
+
+        if needs_bisection {
+            s = (a + b) / 2.0;
+            mflag = true;
+        } else {
+            mflag = false;
+        }
+
+        let fs = f(s);
+        d = c;
+        c = b;
+        fc = fb;
+
+        if fa * fs < 0.0 {
+            b = s;
+            fb = fs;
+        } else {
+            a = s;
+            fa = fs;
+        }
+
+        if fa.abs() < fb.abs() {
+            std::mem::swap(&mut a, &mut b);
+            std::mem::swap(&mut fa, &mut fb);
+        }
+    }
+
     Err(BeckerError::MaxIterationsReached(
-        "Could not find initial bounds",
+        "Brent solver did not converge",
     ))
 }
 
+/// Attempt a Newton-Raphson solve seeded at `irr_guess`, using the
+/// analytic derivative from `becker_obt_with_deriv_rs` for quadratic
+/// convergence. Returns `None` (letting the caller fall back to the
+/// guaranteed-convergence Brent solver) if the derivative vanishes or a
+/// step would leave the `[lo, hi]` bracket, since the piecewise OBt curve
+/// can otherwise send Newton's method off to a spurious root.
+fn newton_solve(
+    earnings: &[f64],
+    int_disc: f64,
+    irr_guess: f64,
+    lo: f64,
+    hi: f64,
+    config: &IrrConfig,
+) -> Option<f64> {
+    let (bracket_lo, bracket_hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+    let mut irr = irr_guess;
+
+    for _ in 0..config.max_iterations {
+        let (obt, d_obt) = becker_obt_with_deriv_rs(earnings, int_disc, irr);
+
+        if obt.abs() < config.tolerance {
+            return Some(irr);
+        }
+
+        if d_obt.abs() < f64::EPSILON {
+            return None;
+        }
+
+        let next_irr = irr - obt / d_obt;
+        if !next_irr.is_finite() || next_irr < bracket_lo || next_irr > bracket_hi {
+            return None;
+        }
+
+        irr = next_irr;
+    }
+
+    None
+}
+
 /// Core implementation of becker_irr
 fn internal_becker_irr(
     earnings: &[f64],
@@ -155,6 +407,12 @@ fn internal_becker_irr(
         ));
     }
 
+    if !int_disc.is_finite() || !irr_guess.is_finite() || earnings.iter().any(|x| !x.is_finite()) {
+        return Err(BeckerError::InvalidInput(
+            "earnings, int_disc, and irr_guess must be finite",
+        ));
+    }
+
     // Handle simple cases
     match earnings.len() {
         1 => {
@@ -176,8 +434,12 @@ fn internal_becker_irr(
     };
 
     // Find initial bounds
-    let bound = find_bounds(earnings, int_disc, irr_guess, &config)?;
-    let (mut irr_a, mut irr_b) = if becker_obt_rs(earnings, int_disc, irr_guess) < 0.0 {
+    let bound = find_bounds(
+        |irr| becker_obt_rs(earnings, int_disc, irr),
+        irr_guess,
+        &config,
+    )?;
+    let (irr_a, irr_b) = if becker_obt_rs(earnings, int_disc, irr_guess) < 0.0 {
         (irr_guess, bound)
     } else {
         (bound, irr_guess)
@@ -188,32 +450,92 @@ fn internal_becker_irr(
         return Ok((irr_a + irr_b) / 2.0);
     }
 
-    // Binary search with adaptive precision
-    for _ in 0..config.max_iterations {
-        let irr_mid = (irr_a + irr_b) / 2.0;
+    // Fast path: quadratic convergence via Newton-Raphson when the
+    // derivative stays well-behaved inside the bracket.
+    if let Some(irr) = newton_solve(earnings, int_disc, irr_guess, irr_a, irr_b, &config) {
+        return Ok(irr);
+    }
 
-        // Early exit if we've reached desired precision
-        if (irr_a - irr_b).abs() <= config.tolerance {
-            return Ok(irr_mid);
-        }
+    brent_solve(
+        |irr| becker_obt_rs(earnings, int_disc, irr),
+        irr_a,
+        irr_b,
+        &config,
+    )
+}
+
+/// Core implementation of becker_irr_dated: solve for the rate against
+/// `becker_obt_periods_rs` instead of the fixed-period `becker_obt_rs`.
+fn internal_becker_irr_dated(
+    earnings: &[f64],
+    periods: &[f64],
+    int_disc: f64,
+    irr_guess: f64,
+    decimals: i32,
+) -> BeckerResult {
+    // Input validation
+    if earnings.is_empty() {
+        return Err(BeckerError::EmptyEarnings);
+    }
 
-        let obt = becker_obt_rs(earnings, int_disc, irr_mid);
+    if earnings.len() != periods.len() {
+        return Err(BeckerError::InvalidInput(
+            "earnings and periods must be the same length",
+        ));
+    }
 
-        // Early exit if we found an exact solution
-        if obt.abs() < config.tolerance {
-            return Ok(irr_mid);
-        }
+    if int_disc < -1.0 || decimals < 0 {
+        return Err(BeckerError::InvalidInput(
+            "Invalid discount rate or decimals",
+        ));
+    }
 
-        if obt < 0.0 {
-            irr_a = irr_mid;
-        } else {
-            irr_b = irr_mid;
+    if !int_disc.is_finite()
+        || !irr_guess.is_finite()
+        || earnings.iter().any(|x| !x.is_finite())
+        || periods.iter().any(|x| !x.is_finite())
+    {
+        return Err(BeckerError::InvalidInput(
+            "earnings, periods, int_disc, and irr_guess must be finite",
+        ));
+    }
+
+    // Handle simple cases
+    match earnings.len() {
+        1 => {
+            return Ok(if earnings[0] == 0.0 {
+                0.0
+            } else if earnings[0] > 0.0 {
+                f64::INFINITY
+            } else {
+                f64::NEG_INFINITY
+            })
         }
+        _ if earnings.iter().all(|&x| x == 0.0) => return Ok(0.0),
+        _ => {}
     }
 
-    Err(BeckerError::MaxIterationsReached(
-        "Binary search did not converge",
-    ))
+    let config = IrrConfig {
+        tolerance: 10.0f64.powi(-decimals),
+        ..IrrConfig::default()
+    };
+
+    let obt_at = |irr| becker_obt_periods_rs(earnings, periods, int_disc, irr);
+
+    // Find initial bounds
+    let bound = find_bounds(obt_at, irr_guess, &config)?;
+    let (irr_a, irr_b) = if obt_at(irr_guess) < 0.0 {
+        (irr_guess, bound)
+    } else {
+        (bound, irr_guess)
+    };
+
+    // Early exit if bounds are already close enough
+    if (irr_a - irr_b).abs() < config.tolerance {
+        return Ok((irr_a + irr_b) / 2.0);
+    }
+
+    brent_solve(obt_at, irr_a, irr_b, &config)
 }
 
 /// Python wrapper for becker_irr
@@ -225,14 +547,184 @@ fn becker_irr(earnings: Vec<f64>, int_disc: f64, irr_guess: f64, decimals: i32)
     }
 }
 
+/// Convert strictly increasing ordinal day numbers into the period gaps
+/// (in fractional years) consumed by `becker_obt_periods_rs`.
+fn dates_to_periods(dates: &[i64]) -> Result<Vec<f64>, BeckerError> {
+    if dates.windows(2).any(|pair| pair[1] <= pair[0]) {
+        return Err(BeckerError::InvalidInput("dates must be strictly increasing"));
+    }
+
+    let mut periods = vec![0.0; dates.len()];
+    for i in 1..dates.len() {
+        periods[i] = (dates[i] - dates[i - 1]) as f64 / 365.0;
+    }
+
+    Ok(periods)
+}
+
+/// Python wrapper for becker_irr_dated
+///
+/// `dates` are proleptic-Gregorian ordinal day numbers (e.g. Python's
+/// `date.toordinal()`), one per earning. Consecutive gaps are converted
+/// to fractional years (days / 365) and fed to `becker_obt_periods_rs` so
+/// cashflows that don't fall on even anniversaries still compound
+/// correctly.
+#[pyfunction]
+fn becker_irr_dated(
+    dates: Vec<i64>,
+    earnings: Vec<f64>,
+    int_disc: f64,
+    irr_guess: f64,
+    decimals: i32,
+) -> PyResult<f64> {
+    if dates.len() != earnings.len() {
+        return Err(BeckerError::InvalidInput("dates and earnings must be the same length").into());
+    }
+
+    let periods = dates_to_periods(&dates)?;
+
+    match internal_becker_irr_dated(&earnings, &periods, int_disc, irr_guess, decimals) {
+        Ok(result) => Ok(result),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Python wrapper for becker_irr_batch
+///
+/// Solves many independent earnings series in parallel with rayon, so
+/// large portfolios (tens of thousands of policies) are solved without
+/// per-call Python overhead. A series whose solve returns any error (e.g.
+/// an empty series, or one that fails to converge) contributes `NaN` to
+/// its slot rather than aborting the whole batch.
+#[pyfunction]
+fn becker_irr_batch(
+    earnings_batch: Vec<Vec<f64>>,
+    int_disc: f64,
+    irr_guess: f64,
+    decimals: i32,
+) -> Vec<f64> {
+    earnings_batch
+        .par_iter()
+        .map(|earnings| {
+            internal_becker_irr(earnings, int_disc, irr_guess, decimals).unwrap_or(f64::NAN)
+        })
+        .collect()
+}
+
 /// Define the Python module
 #[pymodule]
 fn becker_irr_rs(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(becker_irr, m)?)?;
+    m.add_function(wrap_pyfunction!(becker_irr_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(becker_irr_dated, m)?)?;
     m.add_function(wrap_pyfunction!(becker_obt, m)?)?;
+    m.add_function(wrap_pyfunction!(becker_obt_with_deriv, m)?)?;
+    m.add_function(wrap_pyfunction!(becker_obt_periods, m)?)?;
 
     // Add module docstring
     m.add("__doc__", "Rust implementation of Becker IRR calculation")?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE_EARNINGS: [f64; 5] = [-1000.0, 300.0, 300.0, 300.0, 300.0];
+
+    #[test]
+    fn internal_becker_irr_solves_simple_stream() {
+        let irr = internal_becker_irr(&SIMPLE_EARNINGS, 0.05, 0.1, 6).unwrap();
+        assert!(becker_obt_rs(&SIMPLE_EARNINGS, 0.05, irr).abs() < 1e-4);
+    }
+
+    #[test]
+    fn brent_solve_and_newton_solve_agree() {
+        let config = IrrConfig::default();
+        let bound = find_bounds(
+            |irr| becker_obt_rs(&SIMPLE_EARNINGS, 0.05, irr),
+            0.1,
+            &config,
+        )
+        .unwrap();
+
+        let brent_irr = brent_solve(
+            |irr| becker_obt_rs(&SIMPLE_EARNINGS, 0.05, irr),
+            0.1,
+            bound,
+            &config,
+        )
+        .unwrap();
+        let newton_irr = newton_solve(&SIMPLE_EARNINGS, 0.05, 0.1, 0.1, bound, &config).unwrap();
+
+        assert!((brent_irr - newton_irr).abs() < 1e-6);
+    }
+
+    #[test]
+    fn newton_solve_falls_back_when_bracket_excludes_guess() {
+        // The first Newton step from 0.1 lands near 0.078, below this
+        // bracket, so it is rejected and the caller must fall back to Brent.
+        let config = IrrConfig::default();
+        assert!(newton_solve(&SIMPLE_EARNINGS, 0.05, 0.1, 0.1001, 0.2, &config).is_none());
+    }
+
+    #[test]
+    fn becker_irr_batch_reports_nan_for_failing_series_without_aborting() {
+        let batch = vec![SIMPLE_EARNINGS.to_vec(), Vec::new()];
+        let results = becker_irr_batch(batch, 0.05, 0.1, 6);
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_finite());
+        assert!(results[1].is_nan());
+    }
+
+    #[test]
+    fn becker_obt_periods_rs_matches_fixed_period_becker_obt_rs_when_periods_are_one() {
+        let periods = [0.0, 1.0, 1.0, 1.0, 1.0];
+        let fixed = becker_obt_rs(&SIMPLE_EARNINGS, 0.05, 0.1);
+        let dated = becker_obt_periods_rs(&SIMPLE_EARNINGS, &periods, 0.05, 0.1);
+        assert!((fixed - dated).abs() < 1e-12);
+    }
+
+    #[test]
+    fn becker_obt_periods_rs_avoids_nan_when_becker_irr_below_negative_one() {
+        // Regression test: find_bounds routinely probes irr < -1 while
+        // bracketing, which used to send `factor.powf(period)` to NaN.
+        let periods = [0.0, 0.5, 0.5, 0.5, 0.5];
+        let obt = becker_obt_periods_rs(&SIMPLE_EARNINGS, &periods, 0.05, -1.2);
+        assert!(!obt.is_nan());
+    }
+
+    #[test]
+    fn dates_to_periods_rejects_non_monotonic_dates() {
+        assert!(matches!(
+            dates_to_periods(&[1, 5, 3, 10]),
+            Err(BeckerError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn internal_becker_irr_rejects_non_finite_inputs() {
+        let mut earnings = SIMPLE_EARNINGS.to_vec();
+        earnings[1] = f64::NAN;
+        assert!(matches!(
+            internal_becker_irr(&earnings, 0.05, 0.1, 6),
+            Err(BeckerError::InvalidInput(_))
+        ));
+
+        assert!(matches!(
+            internal_becker_irr(&SIMPLE_EARNINGS, f64::INFINITY, 0.1, 6),
+            Err(BeckerError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn internal_becker_irr_dated_rejects_non_finite_periods() {
+        let periods = [0.0, 1.0, f64::NAN, 1.0, 1.0];
+        assert!(matches!(
+            internal_becker_irr_dated(&SIMPLE_EARNINGS, &periods, 0.05, 0.1, 6),
+            Err(BeckerError::InvalidInput(_))
+        ));
+    }
+}